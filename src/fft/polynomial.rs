@@ -0,0 +1,104 @@
+use super::prime_field_element::PrimeFieldElement;
+use super::{intt_fft, ntt_fft};
+
+// Below this many output coefficients the NTT setup (padding, two forward
+// transforms and an inverse) costs more than it saves, so we fall back to the
+// straightforward O(n^2) schoolbook product.
+const NTT_THRESHOLD: usize = 64;
+
+// Multiply two polynomials given as coefficient vectors (index i holds the
+// coefficient of x^i). For small inputs the schoolbook algorithm wins; larger
+// ones are convolved through the NTT: pad both operands to the next power of
+// two at least `len(a) + len(b) - 1`, transform, multiply pointwise, invert and
+// truncate.
+pub fn multiply<'a>(
+    a: &[PrimeFieldElement<'a>],
+    b: &[PrimeFieldElement<'a>],
+) -> Vec<PrimeFieldElement<'a>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let result_len = a.len() + b.len() - 1;
+    if result_len < NTT_THRESHOLD {
+        return schoolbook(a, b);
+    }
+
+    let field = a[0].field;
+    let n = result_len.next_power_of_two();
+    let (omega, _omega_inv) = field
+        .primitive_root_of_unity(n)
+        .expect("field has no primitive root of unity for the padded length");
+
+    let pad = |src: &[PrimeFieldElement<'a>]| -> Vec<PrimeFieldElement<'a>> {
+        let mut padded = Vec::with_capacity(n);
+        padded.extend_from_slice(src);
+        padded.resize(n, PrimeFieldElement::new(0, field));
+        padded
+    };
+
+    let a_hat = ntt_fft(pad(a), &omega);
+    let b_hat = ntt_fft(pad(b), &omega);
+    let product_hat: Vec<PrimeFieldElement<'a>> = a_hat
+        .into_iter()
+        .zip(b_hat.into_iter())
+        .map(|(x, y)| x * y)
+        .collect();
+    let mut product = intt_fft(product_hat, &omega);
+    product.truncate(result_len);
+    product
+}
+
+fn schoolbook<'a>(
+    a: &[PrimeFieldElement<'a>],
+    b: &[PrimeFieldElement<'a>],
+) -> Vec<PrimeFieldElement<'a>> {
+    let field = a[0].field;
+    let mut result = vec![PrimeFieldElement::new(0, field); a.len() + b.len() - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        for (j, &bj) in b.iter().enumerate() {
+            result[i + j] = result[i + j] + ai * bj;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test_polynomial {
+    use super::super::prime_field_element::PrimeField;
+    use super::*;
+
+    #[test]
+    fn multiply_small_schoolbook() {
+        // (1 + 2x)(3 + 4x) = 3 + 10x + 8x^2 over Z_101.
+        let field = PrimeField::new(101);
+        let a = vec![
+            PrimeFieldElement::new(1, &field),
+            PrimeFieldElement::new(2, &field),
+        ];
+        let b = vec![
+            PrimeFieldElement::new(3, &field),
+            PrimeFieldElement::new(4, &field),
+        ];
+        let product = multiply(&a, &b);
+        assert_eq!(
+            product,
+            vec![
+                PrimeFieldElement::new(3, &field),
+                PrimeFieldElement::new(10, &field),
+                PrimeFieldElement::new(8, &field),
+            ]
+        );
+    }
+
+    #[test]
+    fn multiply_ntt_matches_schoolbook() {
+        // p = 2^32 - 2^20 + 1 is NTT-friendly for power-of-two lengths.
+        let field = PrimeField::new(4293918721);
+        let a: Vec<PrimeFieldElement> =
+            (0..100).map(|i| PrimeFieldElement::new(i, &field)).collect();
+        let b: Vec<PrimeFieldElement> = (0..100)
+            .map(|i| PrimeFieldElement::new(2 * i + 1, &field))
+            .collect();
+        assert_eq!(multiply(&a, &b), schoolbook(&a, &b));
+    }
+}
@@ -8,13 +8,151 @@ use std::ops::Sub;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PrimeField {
-    q: i128,
+    pub q: i128,
 }
 
 impl PrimeField {
     pub fn new(q: i128) -> Self {
         Self { q }
     }
+
+    // A multiplicative generator g of Z_q*: for every prime factor p of q-1 the
+    // power g^((q-1)/p) must differ from 1, which guarantees g has full order
+    // q-1 and therefore generates the group.
+    pub fn multiplicative_generator(&self) -> PrimeFieldElement {
+        let factors = distinct_prime_factors(self.q - 1);
+        let one = PrimeFieldElement::new(1, self);
+        for candidate in 2..self.q {
+            let g = PrimeFieldElement::new(candidate, self);
+            if factors
+                .iter()
+                .all(|&p| g.pow(((self.q - 1) / p) as u128) != one)
+            {
+                return g;
+            }
+        }
+        panic!("no multiplicative generator found for q={}", self.q);
+    }
+
+    // The 2-adicity of the field: the largest e with 2^e | q-1, i.e. the
+    // longest power-of-two transform the field can support.
+    pub fn two_adicity(&self) -> u32 {
+        let mut n = self.q - 1;
+        let mut e = 0;
+        while n % 2 == 0 {
+            n /= 2;
+            e += 1;
+        }
+        e
+    }
+
+    // A primitive n-th root of unity together with its inverse, for n a power
+    // of two dividing 2^e (e the 2-adicity). The root is `g^((q-1)/n)` for a
+    // multiplicative generator g and the inverse is the root of the inverse
+    // transform; `None` when n is not such a power of two.
+    pub fn primitive_root_of_unity(
+        &self,
+        n: usize,
+    ) -> Option<(PrimeFieldElement, PrimeFieldElement)> {
+        if n == 0 || !n.is_power_of_two() || n.trailing_zeros() > self.two_adicity() {
+            return None;
+        }
+        let root = self
+            .multiplicative_generator()
+            .pow(((self.q - 1) / n as i128) as u128);
+        Some((root, root.inv()))
+    }
+}
+
+// Distinct prime factors of `n`, found by trial division.
+fn distinct_prime_factors(mut n: i128) -> Vec<i128> {
+    let mut factors = Vec::new();
+    let mut d = 2;
+    while d * d <= n {
+        if n % d == 0 {
+            factors.push(d);
+            while n % d == 0 {
+                n /= d;
+            }
+        }
+        d += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+fn is_prime(n: i128) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut d = 2;
+    while d * d <= n {
+        if n % d == 0 {
+            return false;
+        }
+        d += 1;
+    }
+    true
+}
+
+// Search upward from roughly `bits`-bit integers for an NTT-friendly prime, i.e.
+// a prime q with q ≡ 1 (mod 2^k) so that the field admits a transform of length
+// 2^k. Lets callers set up a working NTT without hand-picking q.
+pub fn ntt_friendly_prime(bits: u32, k: u32) -> PrimeField {
+    let step = 1i128 << k;
+    // First candidate >= 2^bits that is congruent to 1 modulo 2^k.
+    let mut q = (1i128 << bits) + 1;
+    q += (step - (q - 1) % step) % step;
+    while !is_prime(q) {
+        q += step;
+    }
+    PrimeField::new(q)
+}
+
+// A minimal field abstraction so the transform code does not have to name the
+// concrete (and lifetime-bound) `PrimeFieldElement`. The zero/one constructors
+// take `&self` because an element carries the field it lives in; a future
+// fixed-modulus or Montgomery-form representation can implement this trait
+// without the borrowed `&PrimeField` lifetime leaking into the algorithms.
+pub trait Field:
+    Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    fn zero(&self) -> Self;
+    fn one(&self) -> Self;
+    fn is_zero(&self) -> bool;
+    fn add(&self, other: &Self) -> Self;
+    fn sub(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+    fn inv(&self) -> Self;
+    fn primitive_root_of_unity(&self, n: usize) -> Self;
+
+    // Multiplicative inverse; alias of `inv` for callers that prefer the
+    // spelled-out name.
+    fn inverse(&self) -> Self {
+        self.inv()
+    }
+
+    fn square(&self) -> Self {
+        Field::mul(self, self)
+    }
+
+    // Square-and-multiply, expressed purely through the field operations so
+    // every implementor gets it for free. Implementors with a faster routine
+    // (e.g. `PrimeFieldElement::pow_vartime`) may override it.
+    fn pow(&self, mut exp: u128) -> Self {
+        let mut acc = self.one();
+        let mut base = *self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = Field::mul(&acc, &base);
+            }
+            base = Field::mul(&base, &base);
+            exp >>= 1;
+        }
+        acc
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Copy)]
@@ -38,7 +176,7 @@ impl<'a> PrimeFieldElement<'a> {
     }
 
     pub fn legendre_symbol(&self) -> i128 {
-        self.mod_pow((self.field.q - 1) / 2).value
+        self.pow(((self.field.q - 1) / 2) as u128).value
     }
 
     fn same_field_check(&self, other: &PrimeFieldElement, operation: &str) {
@@ -97,22 +235,138 @@ impl<'a> PrimeFieldElement<'a> {
         }
     }
 
-    pub fn mod_pow(&self, pow: i128) -> Self {
-        let mut acc = Self {
-            value: 1,
-            field: self.field,
-        };
-        let res = self.clone();
+    // Modular square root via Tonelli–Shanks. Returns `None` when `self` is a
+    // quadratic non-residue, `Some(0)` for zero, and otherwise a root `r` with
+    // `r * r == self` (the other root is its negation).
+    pub fn sqrt(&self) -> Option<Self> {
+        let q = self.field.q;
+        if self.value == 0 {
+            return Some(*self);
+        }
+        if self.legendre_symbol() != 1 {
+            return None;
+        }
+
+        // Fast path for q ≡ 3 (mod 4).
+        if q % 4 == 3 {
+            return Some(self.pow(((q + 1) / 4) as u128));
+        }
+
+        // Factor q - 1 = s · 2^e with s odd.
+        let mut s = q - 1;
+        let mut e = 0u32;
+        while s % 2 == 0 {
+            s /= 2;
+            e += 1;
+        }
+
+        // Smallest quadratic non-residue z.
+        let mut z = PrimeFieldElement::new(2, self.field);
+        while z.legendre_symbol() != q - 1 {
+            z = PrimeFieldElement::new(z.value + 1, self.field);
+        }
 
-        for i in 0..128 {
-            acc = acc.clone() * acc.clone();
-            let set: bool = pow & (1 << (128 - 1 - i)) != 0;
-            if set {
-                acc = acc * res.clone();
+        let one = PrimeFieldElement::new(1, self.field);
+        let mut c = z.pow(s as u128);
+        let mut x = self.pow(((s + 1) / 2) as u128);
+        let mut t = self.pow(s as u128);
+        let mut m = e;
+        loop {
+            if t == one {
+                return Some(x);
+            }
+            // Least i in 1..m with t^(2^i) == 1.
+            let mut i = 1u32;
+            let mut t2i = t * t;
+            while t2i != one {
+                t2i = t2i * t2i;
+                i += 1;
+            }
+            // b = c^(2^(m-i-1)).
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = b * b;
+            }
+            x = x * b;
+            let b2 = b * b;
+            t = t * b2;
+            c = b2;
+            m = i;
+        }
+    }
+
+    // Variable-time exponentiation by square-and-multiply over the actual bits
+    // of `exp`, a little-endian slice of u64 limbs. Leading zero limbs and bits
+    // are skipped, so small exponents cost only as much as their true bit
+    // length and exponents larger than i128 (needed for bigger moduli) are
+    // expressible.
+    pub fn pow_vartime(&self, exp: &[u64]) -> Self {
+        let mut acc = PrimeFieldElement::new(1, self.field);
+        let mut started = false;
+        for &limb in exp.iter().rev() {
+            for i in (0..64).rev() {
+                let bit = (limb >> i) & 1 == 1;
+                if !started {
+                    if bit {
+                        started = true;
+                        acc = *self;
+                    }
+                } else {
+                    acc = acc * acc;
+                    if bit {
+                        acc = acc * *self;
+                    }
+                }
             }
         }
         acc
     }
+
+    // Convenience wrapper raising to a `u128` exponent.
+    pub fn pow(&self, exp: u128) -> Self {
+        self.pow_vartime(&[exp as u64, (exp >> 64) as u64])
+    }
+}
+
+impl<'a> Field for PrimeFieldElement<'a> {
+    fn zero(&self) -> Self {
+        PrimeFieldElement::new(0, self.field)
+    }
+
+    fn one(&self) -> Self {
+        PrimeFieldElement::new(1, self.field)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        *self + *other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        *self - *other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        *self * *other
+    }
+
+    fn inv(&self) -> Self {
+        PrimeFieldElement::inv(self)
+    }
+
+    fn pow(&self, exp: u128) -> Self {
+        self.pow_vartime(&[exp as u64, (exp >> 64) as u64])
+    }
+
+    fn primitive_root_of_unity(&self, n: usize) -> Self {
+        self.field
+            .primitive_root_of_unity(n)
+            .expect("field has no primitive root of unity for the requested length")
+            .0
+    }
 }
 
 impl<'a> Add for PrimeFieldElement<'a> {
@@ -145,7 +399,7 @@ impl<'a> Mul for PrimeFieldElement<'a> {
     fn mul(self, other: Self) -> Self {
         self.same_field_check(&other, "mul");
         Self {
-            value: self.value * other.value % self.field.q,
+            value: mul_mod(self.value, other.value, self.field.q),
             field: self.field,
         }
     }
@@ -157,12 +411,20 @@ impl<'a> Div for PrimeFieldElement<'a> {
     fn div(self, other: Self) -> Self {
         self.same_field_check(&other, "div");
         Self {
-            value: other.inv().value * self.value % self.field.q,
+            value: mul_mod(other.inv().value, self.value, self.field.q),
             field: self.field,
         }
     }
 }
 
+// `a * b mod q` computed through a 128-bit unsigned product. Both operands are
+// canonical representatives in `[0, q)`, so widening to `u128` keeps the
+// product below `2^128` for every modulus up to `2^64`, where the plain
+// `i128` product `a * b` would overflow once `q` grows past ~2^63.
+fn mul_mod(a: i128, b: i128, q: i128) -> i128 {
+    ((a as u128 * b as u128) % q as u128) as i128
+}
+
 impl<'a> Rem for PrimeFieldElement<'a> {
     type Output = Self;
 
@@ -182,6 +444,45 @@ impl<'a> Rem for PrimeFieldElement<'a> {
 mod test_modular_arithmetic {
     #![allow(clippy::just_underscores_and_digits)]
 
+    #[test]
+    fn large_prime_multiply() {
+        use super::*;
+
+        // A 61-bit prime with two ~60-bit operands: their natural product is
+        // ~2^120. Check against a `u128` reference to confirm `mul_mod`'s
+        // wider intermediate returns the correct residue.
+        let q: i128 = 2_305_843_009_213_693_951; // 2^61 - 1
+        let field = PrimeField::new(q);
+        let x: i128 = 1_152_921_504_606_846_973;
+        let y: i128 = 1_000_000_000_000_000_003;
+        let a = PrimeFieldElement::new(x, &field);
+        let b = PrimeFieldElement::new(y, &field);
+        let expected = (x as u128 * y as u128 % q as u128) as i128;
+        assert_eq!((a * b).value, expected);
+    }
+
+    #[test]
+    fn sqrt() {
+        use super::*;
+
+        // q ≡ 1 (mod 4) exercises the full Tonelli–Shanks path.
+        let field_13 = PrimeField::new(13);
+        let ten = PrimeFieldElement::new(10, &field_13); // 6^2 = 10 (mod 13)
+        let root = ten.sqrt().unwrap();
+        assert_eq!(root * root, ten);
+        assert!(PrimeFieldElement::new(2, &field_13).sqrt().is_none()); // non-residue
+        assert_eq!(
+            PrimeFieldElement::new(0, &field_13).sqrt(),
+            Some(PrimeFieldElement::new(0, &field_13))
+        );
+
+        // q ≡ 3 (mod 4) exercises the fast path.
+        let field_19 = PrimeField::new(19);
+        let five = PrimeFieldElement::new(5, &field_19); // 9^2 = 81 = 5 (mod 19)
+        let root = five.sqrt().unwrap();
+        assert_eq!(root * root, five);
+    }
+
     #[test]
     fn internal() {
         use super::*;